@@ -21,6 +21,12 @@
 //!     // Create a new directory called `/home/user/workspace/middle-earth/.eregion`
 //!     let custom_dir = dotstore::custom_store("/home/user/workspace/middle-earth", "eregion")?;
 //!
+//!     // Or pick the base dir at runtime instead of hard-coding which wrapper to call
+//!     let runtime_dir = dotstore::store(dotstore::BaseDir::Cache, "barracuda")?;
+//!
+//!     // Look up where a store would live without creating it
+//!     let would_be = dotstore::resolve(dotstore::BaseDir::Cache, "barracuda");
+//!
 //!     Ok(())
 //! }
 //! ```
@@ -31,8 +37,20 @@ use std::{
     path::{Path, PathBuf},
 };
 
-#[derive(Debug, PartialEq, Eq)]
-enum StoreType {
+mod project;
+mod resolver;
+#[cfg(test)]
+mod test_support;
+pub use project::ProjectStore;
+pub use resolver::{DefaultResolver, DirResolver};
+
+/// The system location a store is created under.
+///
+/// This mirrors the directory kinds exposed by the [`dirs`] crate, and is the
+/// single entry point for picking a base dynamically (e.g. from config)
+/// instead of hard-coding which `*_store` wrapper to call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseDir {
     Home,
     Config,
     ConfigLocal,
@@ -54,7 +72,7 @@ enum StoreType {
     Video,
 }
 
-impl StoreType {
+impl BaseDir {
     pub fn path(&self) -> fn() -> Option<PathBuf> {
         match *self {
             Self::Home => dirs::home_dir,
@@ -88,11 +106,72 @@ fn create_dir(path: &PathBuf) -> io::Result<()> {
     Ok(())
 }
 
-fn create_store(store: StoreType, path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    let dir_fn = store.path();
-    if let Some(root) = dir_fn() {
-        let store_dir = root.join(format!(".{}", path.as_ref().display()));
-        create_dir(&store_dir)?;
+/// Controls how [`store_with`] names and creates a store directory.
+///
+/// The default mirrors the existing `*_store` wrappers: a leading `.` prefix and the
+/// directory is created. Set `prefix` to `None` to avoid the double-hidden nesting that
+/// results from prefixing a dot directory under a base that's already hidden/app-reserved
+/// (e.g. `config`, `data`, `cache`, `state`), or `create` to `false` to skip creation.
+#[derive(Debug, Clone)]
+pub struct StoreOptions {
+    pub prefix: Option<String>,
+    pub create: bool,
+}
+
+impl Default for StoreOptions {
+    fn default() -> Self {
+        Self {
+            prefix: Some(".".to_string()),
+            create: true,
+        }
+    }
+}
+
+impl StoreOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    pub fn no_prefix(mut self) -> Self {
+        self.prefix = None;
+        self
+    }
+
+    pub fn create(mut self, create: bool) -> Self {
+        self.create = create;
+        self
+    }
+}
+
+fn store_name(path: impl AsRef<Path>, options: &StoreOptions) -> String {
+    match &options.prefix {
+        Some(prefix) => format!("{prefix}{}", path.as_ref().display()),
+        None => path.as_ref().display().to_string(),
+    }
+}
+
+fn create_store(store: BaseDir, path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
+    create_store_with(store, path, &StoreOptions::default())
+}
+
+fn create_store_with(
+    base: BaseDir,
+    path: impl AsRef<Path>,
+    options: &StoreOptions,
+) -> io::Result<Option<PathBuf>> {
+    let name = store_name(path, options);
+    reject_unsafe_store_name(&name)?;
+
+    if let Some(root) = DefaultResolver.resolve(base) {
+        let store_dir = root.join(name);
+        if options.create {
+            create_dir(&store_dir)?;
+        }
 
         Ok(Some(store_dir))
     } else {
@@ -100,99 +179,325 @@ fn create_store(store: StoreType, path: impl AsRef<Path>) -> io::Result<Option<P
     }
 }
 
+/// Creates a new dot directory under `base`, creating it if it doesn't already exist.
+///
+/// This is the generic counterpart to the `*_store` wrappers below, letting callers
+/// choose the base location dynamically at runtime (e.g. from config).
+pub fn store(base: BaseDir, path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
+    create_store(base, path)
+}
+
+/// Like [`store`], but with full control over the naming prefix and whether the
+/// directory is actually created (see [`StoreOptions`]).
+pub fn store_with(
+    base: BaseDir,
+    path: impl AsRef<Path>,
+    options: StoreOptions,
+) -> io::Result<Option<PathBuf>> {
+    create_store_with(base, path, &options)
+}
+
+/// Like [`store_with`], but resolving `base` through `resolver` instead of going straight
+/// to `dirs`. This is what makes the crate testable: supply a [`DirResolver`] backed by a
+/// temp directory in tests instead of touching the real machine.
+pub fn store_with_resolver(
+    resolver: &dyn DirResolver,
+    base: BaseDir,
+    path: impl AsRef<Path>,
+    options: StoreOptions,
+) -> io::Result<Option<PathBuf>> {
+    let name = store_name(path, &options);
+    reject_unsafe_store_name(&name)?;
+
+    if let Some(root) = resolver.resolve(base) {
+        let store_dir = root.join(name);
+        if options.create {
+            create_dir(&store_dir)?;
+        }
+
+        Ok(Some(store_dir))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Like [`resolve_with`], but resolving `base` through `resolver` instead of going
+/// straight to `dirs`.
+pub fn resolve_with_resolver(
+    resolver: &dyn DirResolver,
+    base: BaseDir,
+    path: impl AsRef<Path>,
+    options: &StoreOptions,
+) -> Option<PathBuf> {
+    resolver.resolve(base).map(|root| root.join(store_name(path, options)))
+}
+
+/// Reports whether a dot directory under `base` already exists, without creating it.
+///
+/// Uses the default [`StoreOptions`] prefix; if the store was created via [`store_with`]
+/// with a non-default prefix, use [`exists_with`] instead so the two agree.
+pub fn exists(base: BaseDir, path: impl AsRef<Path>) -> bool {
+    exists_with(base, path, &StoreOptions::default())
+}
+
+/// Like [`exists`], but resolving `path` with the same [`StoreOptions`] the store was
+/// created with.
+pub fn exists_with(base: BaseDir, path: impl AsRef<Path>, options: &StoreOptions) -> bool {
+    resolve_with(base, path, options).is_some_and(|store_dir| store_dir.exists())
+}
+
+/// Like [`exists_with`], but resolving `base` through `resolver` instead of going straight
+/// to `dirs`.
+pub fn exists_with_resolver(
+    resolver: &dyn DirResolver,
+    base: BaseDir,
+    path: impl AsRef<Path>,
+    options: &StoreOptions,
+) -> bool {
+    resolve_with_resolver(resolver, base, path, options).is_some_and(|store_dir| store_dir.exists())
+}
+
+/// Rejects a store name that could escape the resolved base directory, whether it's about
+/// to be created or deleted. Applied on both `create_store_with`/`store_with_resolver` and
+/// `remove_store_with`/`remove_store_with_resolver`, so the crate can never create a store
+/// that it would then refuse to remove.
+///
+/// This must run on the *final* name `store_name` builds (prefix + caller path), not the
+/// caller-supplied path alone: prefixing can turn an innocent-looking input into a traversal,
+/// e.g. path `"."` under the default `.` prefix becomes `".."`, and path `""` becomes `"."`
+/// (the base dir itself). Neither of those is visible by inspecting the raw path.
+fn reject_unsafe_store_name(name: &str) -> io::Result<()> {
+    use std::path::Component;
+
+    let is_unsafe = name.is_empty()
+        || Path::new(name).components().any(|c| {
+            matches!(
+                c,
+                Component::CurDir | Component::ParentDir | Component::RootDir | Component::Prefix(_)
+            )
+        });
+
+    if is_unsafe {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("refusing to create or remove store at unsafe path (resolves to '{name}')"),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Deletes a dot directory under `base` and everything in it.
+///
+/// Returns `Ok(false)` if the store didn't exist, so this is safe to call unconditionally
+/// as part of an uninstall/reset flow. Uses the default [`StoreOptions`] prefix; if the
+/// store was created via [`store_with`] with a non-default prefix, use [`remove_store_with`]
+/// instead so the two agree.
+pub fn remove_store(base: BaseDir, path: impl AsRef<Path>) -> io::Result<bool> {
+    remove_store_with(base, path, &StoreOptions::default())
+}
+
+/// Like [`remove_store`], but resolving `path` with the same [`StoreOptions`] the store was
+/// created with.
+pub fn remove_store_with(
+    base: BaseDir,
+    path: impl AsRef<Path>,
+    options: &StoreOptions,
+) -> io::Result<bool> {
+    reject_unsafe_store_name(&store_name(path.as_ref(), options))?;
+
+    match resolve_with(base, path, options) {
+        Some(store_dir) if store_dir.exists() => {
+            fs::remove_dir_all(&store_dir)?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Like [`remove_store_with`], but resolving `base` through `resolver` instead of going
+/// straight to `dirs`.
+pub fn remove_store_with_resolver(
+    resolver: &dyn DirResolver,
+    base: BaseDir,
+    path: impl AsRef<Path>,
+    options: &StoreOptions,
+) -> io::Result<bool> {
+    reject_unsafe_store_name(&store_name(path.as_ref(), options))?;
+
+    match resolve_with_resolver(resolver, base, path, options) {
+        Some(store_dir) if store_dir.exists() => {
+            fs::remove_dir_all(&store_dir)?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Lists the stores present directly under `base` whose name matches the `StoreOptions`
+/// prefix (the default `.` prefix, matching the `*_store` wrappers). When `options.prefix`
+/// is `None`, every subdirectory of `base` is considered a store, since there's no marker
+/// left to tell a `no_prefix()` store apart from an unrelated directory.
+pub fn enumerate(base: BaseDir) -> io::Result<Vec<PathBuf>> {
+    enumerate_with(base, &StoreOptions::default())
+}
+
+/// Like [`enumerate`], but matching stores created with a non-default [`StoreOptions`]
+/// prefix.
+pub fn enumerate_with(base: BaseDir, options: &StoreOptions) -> io::Result<Vec<PathBuf>> {
+    enumerate_under(DefaultResolver.resolve(base), options)
+}
+
+/// Like [`enumerate_with`], but resolving `base` through `resolver` instead of going
+/// straight to `dirs`.
+pub fn enumerate_with_resolver(
+    resolver: &dyn DirResolver,
+    base: BaseDir,
+    options: &StoreOptions,
+) -> io::Result<Vec<PathBuf>> {
+    enumerate_under(resolver.resolve(base), options)
+}
+
+fn enumerate_under(root: Option<PathBuf>, options: &StoreOptions) -> io::Result<Vec<PathBuf>> {
+    let Some(root) = root else {
+        return Ok(Vec::new());
+    };
+
+    if !root.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut stores = Vec::new();
+    for entry in fs::read_dir(root)? {
+        let path = entry?.path();
+        let matches_prefix = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| match &options.prefix {
+                Some(prefix) => name.starts_with(prefix.as_str()),
+                None => true,
+            });
+
+        if path.is_dir() && matches_prefix {
+            stores.push(path);
+        }
+    }
+
+    Ok(stores)
+}
+
+/// Returns the path a dot directory under `base` would have, without creating anything.
+///
+/// Useful when you only need to check where a store lives (e.g. to decide whether to
+/// clean it up) without triggering the directory creation that [`store`] performs.
+pub fn resolve(base: BaseDir, path: impl AsRef<Path>) -> Option<PathBuf> {
+    resolve_with(base, path, &StoreOptions::default())
+}
+
+/// Like [`resolve`], but with the same naming control as [`store_with`] (see
+/// [`StoreOptions`]). Use this to resolve a store that was created via `store_with` with a
+/// non-default prefix.
+pub fn resolve_with(
+    base: BaseDir,
+    path: impl AsRef<Path>,
+    options: &StoreOptions,
+) -> Option<PathBuf> {
+    DefaultResolver.resolve(base).map(|root| root.join(store_name(path, options)))
+}
+
 /// Creates a new dot directory in the systems Audio path (See [`dirs::audio_dir`])
 pub fn audio_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Audio, path)
+    create_store(BaseDir::Audio, path)
 }
 
 /// Creates a new dot directory in the systems Cache path (See [`dirs::cache_dir`])
 pub fn cache_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Cache, path)
+    create_store(BaseDir::Cache, path)
 }
 
 /// Creates a new dot directory in the systems Config path (See [`dirs::config_dir`])
 pub fn config_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Config, path)
+    create_store(BaseDir::Config, path)
 }
 
 /// Creates a new dot directory in the systems local Config path (See [`dirs::config_local_dir`])
 pub fn local_config_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::ConfigLocal, path)
+    create_store(BaseDir::ConfigLocal, path)
 }
 
 /// Creates a new dot directory in the systems Data path (See [`dirs::data_dir`])
 pub fn data_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Data, path)
+    create_store(BaseDir::Data, path)
 }
 
 /// Creates a new dot directory in the systems local Data path (See [`dirs::data_local_dir`])
 pub fn local_data_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::DataLocal, path)
+    create_store(BaseDir::DataLocal, path)
 }
 
 /// Creates a new dot directory in the systems Desktop path (See [`dirs::desktop_dir`])
 pub fn desktop_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Desktop, path)
+    create_store(BaseDir::Desktop, path)
 }
 
 /// Creates a new dot directory in the systems Document path (See [`dirs::document_dir`])
 pub fn document_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Document, path)
+    create_store(BaseDir::Document, path)
 }
 
 /// Creates a new dot directory in the systems Download path (See [`dirs::download_dir`])
 pub fn download_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Download, path)
+    create_store(BaseDir::Download, path)
 }
 
 /// Creates a new dot directory in the systems Executable path (See [`dirs::executable_dir`])
 pub fn executable_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Executable, path)
+    create_store(BaseDir::Executable, path)
 }
 
 /// Creates a new dot directory in the systems Font path (See [`dirs::font_dir`])
 pub fn font_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Font, path)
+    create_store(BaseDir::Font, path)
 }
 
 /// Creates a new dot directory in the systems Home path (See [`dirs::home_dir`])
 pub fn home_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Home, path)
+    create_store(BaseDir::Home, path)
 }
 
 /// Creates a new dot directory in the systems Picture path (See [`dirs::picture_dir`])
 pub fn picture_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Picture, path)
+    create_store(BaseDir::Picture, path)
 }
 
 /// Creates a new dot directory in the systems Preference path (See [`dirs::picture_dir`])
 pub fn preference_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Preference, path)
+    create_store(BaseDir::Preference, path)
 }
 
 /// Creates a new dot directory in the systems Public path (See [`dirs::public_dir`])
 pub fn public_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Public, path)
+    create_store(BaseDir::Public, path)
 }
 
 /// Creates a new dot directory in the systems Runtime path (See [`dirs::runtime_dir`])
 pub fn runtime_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Runtime, path)
+    create_store(BaseDir::Runtime, path)
 }
 
 /// Creates a new dot directory in the systems State path (See [`dirs::runtime_dir`])
 pub fn state_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::State, path)
+    create_store(BaseDir::State, path)
 }
 
 /// Creates a new dot directory in the systems Template path (See [`dirs::template_dir`])
 pub fn template_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Template, path)
+    create_store(BaseDir::Template, path)
 }
 
 /// Creates a new dot directory in the systems Video path (See [`dirs::video_dir`])
 pub fn video_store(path: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
-    create_store(StoreType::Video, path)
+    create_store(BaseDir::Video, path)
 }
 
 /// Create a new dot directory in a custom location of your choosing.
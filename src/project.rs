@@ -0,0 +1,234 @@
+//! Project-scoped directory layout, modeled on the `directories` crate's `ProjectDirs`.
+//!
+//! Unlike the free functions in the crate root, which each resolve a single independent
+//! dot directory, [`ProjectStore`] groups all of an application's directories under one
+//! coherent identity derived from a qualifier, organization and application name.
+
+use crate::{BaseDir, DefaultResolver, DirResolver};
+use std::fs;
+use std::path::PathBuf;
+
+fn create_dir(path: PathBuf) -> Option<PathBuf> {
+    if !path.exists() {
+        fs::create_dir_all(&path).ok()?;
+    }
+
+    Some(path)
+}
+
+/// A coherent set of per-application directories, keyed by qualifier, organization and
+/// application name (e.g. `ProjectStore::new("com", "Acme", "Editor")`).
+pub struct ProjectStore {
+    // `qualifier` is only read on macOS (bundle id); `organization` on macOS and Windows
+    // (path segments). The Linux/XDG layout keys off `application` alone.
+    #[allow(dead_code)]
+    qualifier: String,
+    #[allow(dead_code)]
+    organization: String,
+    application: String,
+    resolver: Box<dyn DirResolver>,
+}
+
+impl ProjectStore {
+    pub fn new(
+        qualifier: impl Into<String>,
+        organization: impl Into<String>,
+        application: impl Into<String>,
+    ) -> Self {
+        Self::with_resolver(qualifier, organization, application, DefaultResolver)
+    }
+
+    /// Like [`ProjectStore::new`], but resolving base directories through `resolver`
+    /// instead of going straight to `dirs`. This is what makes `ProjectStore` testable:
+    /// supply a [`DirResolver`] backed by a temp directory in tests instead of touching
+    /// the real machine.
+    pub fn with_resolver(
+        qualifier: impl Into<String>,
+        organization: impl Into<String>,
+        application: impl Into<String>,
+        resolver: impl DirResolver + 'static,
+    ) -> Self {
+        Self {
+            qualifier: qualifier.into(),
+            organization: organization.into(),
+            application: application.into(),
+            resolver: Box::new(resolver),
+        }
+    }
+
+    /// The directory for configuration, creating it on first use.
+    pub fn config_dir(&self) -> Option<PathBuf> {
+        create_dir(self.base(DirKind::Config)?)
+    }
+
+    /// The directory for application data, creating it on first use.
+    pub fn data_dir(&self) -> Option<PathBuf> {
+        create_dir(self.base(DirKind::Data)?)
+    }
+
+    /// The directory for non-essential, cached data, creating it on first use.
+    pub fn cache_dir(&self) -> Option<PathBuf> {
+        create_dir(self.base(DirKind::Cache)?)
+    }
+
+    /// The directory for state that should persist between application runs, creating it
+    /// on first use. Only populated on Linux/XDG platforms, mirroring `directories`.
+    pub fn state_dir(&self) -> Option<PathBuf> {
+        create_dir(self.base(DirKind::State)?)
+    }
+
+    /// The directory for non-essential runtime data, creating it on first use. Only
+    /// populated on Linux/XDG platforms, mirroring `directories`.
+    pub fn runtime_dir(&self) -> Option<PathBuf> {
+        create_dir(self.base(DirKind::Runtime)?)
+    }
+
+    /// The directory for user-facing preferences, creating it on first use. Only
+    /// populated on macOS, mirroring `directories`' `preference_dir`.
+    pub fn preference_dir(&self) -> Option<PathBuf> {
+        create_dir(self.base(DirKind::Preference)?)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn bundle_id(&self) -> String {
+        format!("{}.{}.{}", self.qualifier, self.organization, self.application)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn base(&self, kind: DirKind) -> Option<PathBuf> {
+        let home = self.resolver.resolve(BaseDir::Home)?;
+        let id = self.bundle_id();
+        match kind {
+            DirKind::Config | DirKind::Data => {
+                Some(home.join("Library/Application Support").join(id))
+            }
+            DirKind::Cache => Some(home.join("Library/Caches").join(id)),
+            DirKind::Preference => Some(home.join("Library/Preferences").join(id)),
+            DirKind::State | DirKind::Runtime => None,
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn base(&self, kind: DirKind) -> Option<PathBuf> {
+        match kind {
+            DirKind::Config => self
+                .resolver
+                .resolve(BaseDir::Config)
+                .map(|p| p.join(&self.organization).join(&self.application).join("config")),
+            DirKind::Data => self
+                .resolver
+                .resolve(BaseDir::Data)
+                .map(|p| p.join(&self.organization).join(&self.application).join("data")),
+            DirKind::Cache => self
+                .resolver
+                .resolve(BaseDir::Cache)
+                .map(|p| p.join(&self.organization).join(&self.application).join("cache")),
+            DirKind::State | DirKind::Runtime | DirKind::Preference => None,
+        }
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn base(&self, kind: DirKind) -> Option<PathBuf> {
+        let app = self.application.to_lowercase().replace(' ', "-");
+        match kind {
+            DirKind::Config => self.resolver.resolve(BaseDir::Config).map(|p| p.join(&app)),
+            DirKind::Data => self.resolver.resolve(BaseDir::Data).map(|p| p.join(&app)),
+            DirKind::Cache => self.resolver.resolve(BaseDir::Cache).map(|p| p.join(&app)),
+            DirKind::State => self.resolver.resolve(BaseDir::State).map(|p| p.join(&app)),
+            DirKind::Runtime => self.resolver.resolve(BaseDir::Runtime).map(|p| p.join(&app)),
+            DirKind::Preference => None,
+        }
+    }
+}
+
+enum DirKind {
+    Config,
+    Data,
+    Cache,
+    State,
+    Runtime,
+    Preference,
+}
+
+#[cfg(all(test, unix, not(target_os = "macos")))]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+
+    /// Resolves each [`BaseDir`] to its own fixed, temp-dir-backed root, so a single
+    /// `ProjectStore` under test can have distinct config/data/cache/state/runtime roots
+    /// without touching real process-global env vars.
+    struct FakeResolver {
+        config: PathBuf,
+        data: PathBuf,
+        cache: PathBuf,
+        state: PathBuf,
+        runtime: PathBuf,
+    }
+
+    impl DirResolver for FakeResolver {
+        fn resolve(&self, base: BaseDir) -> Option<PathBuf> {
+            match base {
+                BaseDir::Config => Some(self.config.clone()),
+                BaseDir::Data => Some(self.data.clone()),
+                BaseDir::Cache => Some(self.cache.clone()),
+                BaseDir::State => Some(self.state.clone()),
+                BaseDir::Runtime => Some(self.runtime.clone()),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn config_data_cache_dirs_follow_resolver_and_app_name() {
+        let config_home = unique_temp_dir("config");
+        let data_home = unique_temp_dir("data");
+        let cache_home = unique_temp_dir("cache");
+
+        let project = ProjectStore::with_resolver(
+            "com",
+            "Acme",
+            "My Editor",
+            FakeResolver {
+                config: config_home.clone(),
+                data: data_home.clone(),
+                cache: cache_home.clone(),
+                state: unique_temp_dir("unused-state"),
+                runtime: unique_temp_dir("unused-runtime"),
+            },
+        );
+
+        assert_eq!(project.config_dir(), Some(config_home.join("my-editor")));
+        assert_eq!(project.data_dir(), Some(data_home.join("my-editor")));
+        assert_eq!(project.cache_dir(), Some(cache_home.join("my-editor")));
+
+        fs::remove_dir_all(&config_home).unwrap();
+        fs::remove_dir_all(&data_home).unwrap();
+        fs::remove_dir_all(&cache_home).unwrap();
+    }
+
+    #[test]
+    fn state_and_runtime_dirs_follow_resolver_too() {
+        let state_home = unique_temp_dir("state");
+        let runtime_dir = unique_temp_dir("runtime");
+
+        let project = ProjectStore::with_resolver(
+            "com",
+            "Acme",
+            "Editor",
+            FakeResolver {
+                config: unique_temp_dir("unused-config"),
+                data: unique_temp_dir("unused-data"),
+                cache: unique_temp_dir("unused-cache"),
+                state: state_home.clone(),
+                runtime: runtime_dir.clone(),
+            },
+        );
+
+        assert_eq!(project.state_dir(), Some(state_home.join("editor")));
+        assert_eq!(project.runtime_dir(), Some(runtime_dir.join("editor")));
+
+        fs::remove_dir_all(&state_home).unwrap();
+        fs::remove_dir_all(&runtime_dir).unwrap();
+    }
+}
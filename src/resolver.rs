@@ -0,0 +1,211 @@
+//! Injectable base-directory resolution.
+//!
+//! Every `*_store` function and [`crate::store`] ultimately ask "where does `dirs` say
+//! this base dir lives?" against the real machine, which makes them impossible to unit
+//! test hermetically. [`DirResolver`] pulls that lookup out behind a trait so callers
+//! (and tests) can supply their own, e.g. backed by a temp directory.
+
+use crate::BaseDir;
+use std::path::PathBuf;
+
+/// Resolves a [`BaseDir`] to a concrete path.
+pub trait DirResolver {
+    fn resolve(&self, base: BaseDir) -> Option<PathBuf>;
+}
+
+/// The resolver used by default throughout the crate.
+///
+/// Delegates to [`dirs`], but on Linux/BSD-style unix platforms first checks the relevant
+/// XDG environment variable (`XDG_CONFIG_HOME`, `XDG_DATA_HOME`, `XDG_CACHE_HOME`,
+/// `XDG_STATE_HOME`, `XDG_RUNTIME_DIR`), falling back to the `dirs` default (e.g.
+/// `~/.config`, `~/.local/share`) when the variable is unset or not an absolute path.
+/// macOS and Windows never read these vars, matching `dirs`' own per-platform backends.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultResolver;
+
+impl DirResolver for DefaultResolver {
+    fn resolve(&self, base: BaseDir) -> Option<PathBuf> {
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            if let Some(path) = xdg_override(&base) {
+                return Some(path);
+            }
+        }
+
+        base.path()()
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn xdg_override(base: &BaseDir) -> Option<PathBuf> {
+    let var = match base {
+        BaseDir::Config | BaseDir::ConfigLocal => "XDG_CONFIG_HOME",
+        BaseDir::Data | BaseDir::DataLocal => "XDG_DATA_HOME",
+        BaseDir::Cache => "XDG_CACHE_HOME",
+        BaseDir::State => "XDG_STATE_HOME",
+        BaseDir::Runtime => "XDG_RUNTIME_DIR",
+        _ => return None,
+    };
+
+    std::env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+    use crate::{
+        enumerate_with_resolver, exists_with_resolver, remove_store_with_resolver,
+        store_with_resolver, StoreOptions,
+    };
+
+    struct FakeResolver(PathBuf);
+
+    impl DirResolver for FakeResolver {
+        fn resolve(&self, _base: BaseDir) -> Option<PathBuf> {
+            Some(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn store_with_resolver_creates_under_injected_root() {
+        let root = unique_temp_dir("root");
+        std::fs::create_dir_all(&root).unwrap();
+        let resolver = FakeResolver(root.clone());
+
+        let store_dir =
+            store_with_resolver(&resolver, BaseDir::Config, "app", StoreOptions::default())
+                .unwrap()
+                .expect("fake resolver always returns a root");
+
+        assert_eq!(store_dir, root.join(".app"));
+        assert!(store_dir.is_dir());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn lifecycle_round_trips_through_a_fake_resolver() {
+        let root = unique_temp_dir("lifecycle");
+        std::fs::create_dir_all(&root).unwrap();
+        let resolver = FakeResolver(root.clone());
+        let options = StoreOptions::default();
+
+        assert!(!exists_with_resolver(&resolver, BaseDir::Config, "app", &options));
+
+        let store_dir =
+            store_with_resolver(&resolver, BaseDir::Config, "app", options.clone())
+                .unwrap()
+                .expect("fake resolver always returns a root");
+        assert!(store_dir.is_dir());
+
+        assert!(exists_with_resolver(&resolver, BaseDir::Config, "app", &options));
+        assert_eq!(
+            enumerate_with_resolver(&resolver, BaseDir::Config, &options).unwrap(),
+            vec![store_dir.clone()]
+        );
+
+        let removed =
+            remove_store_with_resolver(&resolver, BaseDir::Config, "app", &options).unwrap();
+        assert!(removed);
+        assert!(!store_dir.exists());
+        assert!(!exists_with_resolver(&resolver, BaseDir::Config, "app", &options));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn remove_store_rejects_traversal_and_self_referencing_paths() {
+        let root = unique_temp_dir("traversal");
+        std::fs::create_dir_all(&root).unwrap();
+        let resolver = FakeResolver(root.clone());
+        let options = StoreOptions::default();
+
+        // `"."` under the default `.` prefix resolves to `".."` (`.` + `.`), escaping `root`.
+        // Note `".."` itself is NOT unsafe here: prefixing is string concatenation, not path
+        // joining, so `store_name("..", default)` is `"..."` — an ordinary directory name.
+        let err = remove_store_with_resolver(&resolver, BaseDir::Config, ".", &options)
+            .expect_err("'.' must be rejected before touching the filesystem");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        // `""` under the default `.` prefix resolves to `"."`, i.e. `root` itself.
+        let err = remove_store_with_resolver(&resolver, BaseDir::Config, "", &options)
+            .expect_err("'' must be rejected before touching the filesystem");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        // `root` itself is untouched.
+        assert!(root.is_dir());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn store_options_prefix_controls_are_reflected_in_the_resolved_path() {
+        let root = unique_temp_dir("prefix-options");
+        std::fs::create_dir_all(&root).unwrap();
+        let resolver = FakeResolver(root.clone());
+
+        // Default: leading `.`, e.g. `~/.config/.editor` (the double-hidden-nesting problem).
+        let dotted =
+            store_with_resolver(&resolver, BaseDir::Config, "editor", StoreOptions::default())
+                .unwrap()
+                .expect("fake resolver always returns a root");
+        assert_eq!(dotted, root.join(".editor"));
+
+        // `no_prefix()` fixes it: `~/.config/editor`, no nested hidden directory.
+        let undotted = store_with_resolver(
+            &resolver,
+            BaseDir::Config,
+            "editor",
+            StoreOptions::new().no_prefix(),
+        )
+        .unwrap()
+        .expect("fake resolver always returns a root");
+        assert_eq!(undotted, root.join("editor"));
+        assert_ne!(dotted, undotted);
+
+        // A custom prefix is honored too.
+        let custom = store_with_resolver(
+            &resolver,
+            BaseDir::Config,
+            "editor",
+            StoreOptions::new().prefix("my-"),
+        )
+        .unwrap()
+        .expect("fake resolver always returns a root");
+        assert_eq!(custom, root.join("my-editor"));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    // Env vars are process-global; hold the shared lock for the whole test so this
+    // doesn't race any other test that takes the same lock to touch `XDG_*` vars under
+    // parallel test execution.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    #[test]
+    fn xdg_override_respects_documented_rules() {
+        let _guard = crate::test_support::lock_env();
+        let dir = unique_temp_dir("xdg-config");
+
+        // SAFETY: this test holds `ENV_LOCK` for the duration of all `XDG_CONFIG_HOME`
+        // mutation below.
+        unsafe {
+            std::env::set_var("XDG_CONFIG_HOME", &dir);
+        }
+        assert_eq!(xdg_override(&BaseDir::Config), Some(dir.clone()));
+
+        unsafe {
+            std::env::set_var("XDG_CONFIG_HOME", "relative/path");
+        }
+        assert_eq!(xdg_override(&BaseDir::Config), None);
+
+        unsafe {
+            std::env::remove_var("XDG_CONFIG_HOME");
+        }
+        assert_eq!(xdg_override(&BaseDir::Config), None);
+
+        assert_eq!(xdg_override(&BaseDir::Home), None);
+    }
+}
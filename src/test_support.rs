@@ -0,0 +1,24 @@
+//! Shared helpers for the hermetic tests in `resolver` and `project`.
+#![cfg(test)]
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+/// Serializes tests that mutate process-global `XDG_*` env vars so they don't race
+/// under cargo's default parallel test execution, even across modules.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Acquires the shared env-var lock, recovering from poisoning (a prior test panicking
+/// while holding it shouldn't fail every test after it).
+pub(crate) fn lock_env() -> MutexGuard<'static, ()> {
+    ENV_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Returns a unique scratch directory under the OS temp dir; no tempfile dependency is
+/// available, so this rolls unique names by hand.
+pub(crate) fn unique_temp_dir(label: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!("dotstore-test-{label}-{}-{id}", std::process::id()))
+}